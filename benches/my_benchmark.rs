@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
 use rand_pcg::Pcg64;
-use rand_indices::rand_indices::RngExt;
+use rand_indices::rand_indices::{RngExt, DistinctTupleExcept};
 use rand::SeedableRng;
 
 fn bench(c: &mut Criterion) {
@@ -10,6 +10,12 @@ fn bench(c: &mut Criterion) {
                          |b| b.iter(|| rng.random_distinct_index_tuple_ordered_except_good(5, (0, 2))));
     group.bench_function(BenchmarkId::new("Non-Uniform", 0),
                          |b| b.iter(|| rng.random_distinct_index_tuple_ordered_except_fast(5, (0, 2))));
+
+    let distribution = DistinctTupleExcept::new(5, (0, 2));
+    group.bench_function(BenchmarkId::new("Fast-32bit", 0),
+                         |b| b.iter(|| distribution.sample_32(&mut rng)));
+    group.bench_function(BenchmarkId::new("Fast-64bit", 0),
+                         |b| b.iter(|| distribution.sample_64(&mut rng)));
     group.finish();
 }
 
@@ -1,6 +1,8 @@
 use rand::Rng;
-use rand::seq::index::sample_weighted;
+use rand::distributions::{Distribution, WeightedIndex};
+use std::collections::{BTreeSet, HashMap};
 use std::mem;
+use std::ops::Range;
 
 fn validate_inputs(
     len: usize,
@@ -29,6 +31,240 @@ fn validate_inputs(
     }
 }
 
+fn validate_weighted_inputs(weights: &[f64], deny: (usize, usize)) {
+    validate_inputs(weights.len(), deny);
+    if weights.iter().filter(|&&w| w > 0.0).count() < 2 {
+        panic!("at least two indices must have positive weight, got {:?}", weights)
+    }
+}
+
+/// Binomial coefficient `C(n, k)`, computed with the standard multiplicative
+/// formula so every intermediate product stays an integer. Uses `u128` so
+/// `n` can grow well beyond what fits in a `usize` weight.
+fn binomial(n: usize, k: usize) -> u128 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * (n - i) as u128 / (i + 1) as u128;
+    }
+    result
+}
+
+fn validate_combination_inputs(len: usize, k: usize, deny: &[usize]) {
+    if k == 0 {
+        panic!("k must be at least 1")
+    }
+    if len < k + 1 {
+        panic!("not enough indices to pick from: need len >= k+1, got len={len}, k={k}")
+    }
+    if deny.len() != k {
+        panic!("deny must contain exactly k={k} indices, got {}", deny.len())
+    }
+    let mut deny_sorted = deny.to_vec();
+    deny_sorted.sort_unstable();
+    deny_sorted.dedup();
+    if deny_sorted.len() != k {
+        panic!("denied indices must be distinct")
+    }
+    if let Some(&max) = deny_sorted.last() {
+        if max >= len {
+            panic!(
+                "combination {:?} is not fully contained in range {:?}",
+                deny,
+                0..len
+            )
+        }
+    }
+}
+
+/// Rank of a sorted `k`-combination in the combinatorial number system:
+/// `r = sum over j=1..k of C(sorted[j-1], j)`.
+fn rank_combination(sorted: &[usize]) -> u128 {
+    let mut r: u128 = 0;
+    for (j, &c) in sorted.iter().enumerate() {
+        r += binomial(c, j + 1);
+    }
+    r
+}
+
+/// Inverse of [`rank_combination`]: unrank `t` back into a sorted `k`-combination.
+fn unrank_combination(mut t: u128, len: usize, k: usize) -> Vec<usize> {
+    let mut result = vec![0usize; k];
+    let mut upper = len;
+    for position in (1..=k).rev() {
+        let mut c = upper - 1;
+        while binomial(c, position) > t {
+            c -= 1;
+        }
+        result[position - 1] = c;
+        t -= binomial(c, position);
+        upper = c;
+    }
+    result
+}
+
+/// A precomputed distribution over ordered pairs `(i, j)` with `i < j`, both
+/// drawn from `0..len`, excluding one denied pair. Construct once and sample
+/// many times to avoid recomputing the pair's rank on every draw.
+///
+/// Implemented rejection-free via the same combinatorial-number-system trick
+/// [`RngExt::random_distinct_index_combination_except`] uses for `k`-combinations,
+/// specialized to pairs via [`pair_rank`]/[`pair_unrank`]: draw a rank uniformly
+/// from `0..C(len,2)-1` and shift ranks at or above the denied one up by one.
+pub struct DistinctTupleExcept {
+    len: usize,
+    total: usize,
+    denied_rank: usize,
+}
+
+impl DistinctTupleExcept {
+    pub fn new(len: usize, deny: (usize, usize)) -> Self {
+        validate_inputs(len, deny);
+        let (a, b) = if deny.0 < deny.1 { deny } else { (deny.1, deny.0) };
+        let total = len * (len - 1) / 2;
+        DistinctTupleExcept { len, total, denied_rank: pair_rank(len, a, b) }
+    }
+
+    /// Sample using a plain `usize` range draw. Correct for any `len`, including
+    /// ones whose pair count `C(len, 2)` doesn't fit in a `u32`.
+    pub fn sample_64<R: Rng + ?Sized>(&self, rng: &mut R) -> (usize, usize) {
+        let mut t = rng.gen_range(0..self.total - 1);
+        if t >= self.denied_rank {
+            t += 1;
+        }
+        pair_unrank(t, self.len)
+    }
+
+    /// Sample using the 32-bit Lemire path from [`gen_range_u32`]. `rand`'s own
+    /// sequence sampling is explicitly tuned for 32-bit generators on all
+    /// platforms, so hot loops whose pair count fits in a `u32` take this
+    /// narrower, allocation-free path instead of widening every draw to `usize`.
+    ///
+    /// Panics if `C(self.len, 2)` does not fit in a `u32`; callers should check
+    /// `u32::try_from(total)` first, which is exactly what [`Self::sample`] (the
+    /// `Distribution` impl) does to pick between this and [`Self::sample_64`].
+    pub fn sample_32<R: Rng + ?Sized>(&self, rng: &mut R) -> (usize, usize) {
+        let total = u32::try_from(self.total).expect("C(len, 2) must fit in u32");
+        let denied_rank = self.denied_rank as u32;
+
+        let mut t = gen_range_u32(rng, 0..total - 1);
+        if t >= denied_rank {
+            t += 1;
+        }
+        pair_unrank(t as usize, self.len)
+    }
+}
+
+impl Distribution<(usize, usize)> for DistinctTupleExcept {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (usize, usize) {
+        if u32::try_from(self.total).is_ok() {
+            self.sample_32(rng)
+        } else {
+            self.sample_64(rng)
+        }
+    }
+}
+
+/// Draw an unbiased random `u32` in `range` using Lemire's method: a single
+/// widening multiply, with rejection only in the rare case the low bits fall
+/// below the range's bias threshold. This is the narrow-integer path `rand`
+/// itself favors for sequence sampling, spelled out here so it can be reused
+/// directly by `usize` callers whose `len` fits in a `u32`.
+fn gen_range_u32<R: Rng + ?Sized>(rng: &mut R, range: Range<u32>) -> u32 {
+    let span = range.end - range.start;
+    let mut m = (rng.gen::<u32>() as u64) * (span as u64);
+    if (m as u32) < span {
+        let threshold = span.wrapping_neg() % span;
+        while (m as u32) < threshold {
+            m = (rng.gen::<u32>() as u64) * (span as u64);
+        }
+    }
+    range.start + (m >> 32) as u32
+}
+
+/// Lexicographic rank of the ordered pair `(i, j)` with `i < j` among all
+/// `C(len, 2)` such pairs over `0..len`.
+fn pair_rank(len: usize, i: usize, j: usize) -> usize {
+    let mut rank = 0;
+    for x in 0..i {
+        rank += len - 1 - x;
+    }
+    rank + (j - i - 1)
+}
+
+/// Inverse of [`pair_rank`]: unrank `rank` back into an ordered pair `(i, j)`.
+fn pair_unrank(mut rank: usize, len: usize) -> (usize, usize) {
+    let mut i = 0;
+    loop {
+        let count = len - 1 - i;
+        if rank < count {
+            return (i, i + 1 + rank);
+        }
+        rank -= count;
+        i += 1;
+    }
+}
+
+fn validate_many_inputs(len: usize, deny: &[(usize, usize)]) {
+    if len < 3 {
+        panic!("not enough indices to pick from")
+    }
+    for &(a, b) in deny {
+        if a == b {
+            panic!("denied indices must be distinct")
+        }
+        if a >= len || b >= len {
+            panic!(
+                "tuple {:?} is not fully contained in range {:?}",
+                (a, b),
+                0..len
+            )
+        }
+    }
+}
+
+/// Lazily yields every non-denied ordered pair over `0..len` exactly once, in a
+/// uniformly random order, without materializing the `C(len, 2)` pair space.
+///
+/// Implemented as an online Fisher–Yates shuffle over the implicit rank space
+/// `0..C(len, 2)`: each draw picks a random rank from the shrinking prefix of
+/// not-yet-drawn ranks and swaps it to the back, tracking only the ranks that have been touched so far
+/// in a `HashMap` rather than a full `Vec`.
+pub struct ShuffledDistinctTuples<'r, R: ?Sized> {
+    rng: &'r mut R,
+    len: usize,
+    deny_rank: usize,
+    n: usize,
+    remap: HashMap<usize, usize>,
+}
+
+impl<'r, R: Rng + ?Sized> ShuffledDistinctTuples<'r, R> {
+    fn slot(&self, i: usize) -> usize {
+        self.remap.get(&i).copied().unwrap_or(i)
+    }
+}
+
+impl<'r, R: Rng + ?Sized> Iterator for ShuffledDistinctTuples<'r, R> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        while self.n > 0 {
+            let i = self.rng.gen_range(0..self.n);
+            let rank = self.slot(i);
+            let last = self.n - 1;
+            self.remap.insert(i, self.slot(last));
+            self.n = last;
+            if rank != self.deny_rank {
+                return Some(pair_unrank(rank, self.len));
+            }
+        }
+        None
+    }
+}
+
 pub trait RngExt: Rng {
     /// Select two distinct indices from 0..len. Return the indices as an ordered tuple.
     /// Never return the given `deny` tuple.
@@ -60,34 +296,125 @@ pub trait RngExt: Rng {
         len: usize,
         deny: (usize, usize),
     ) -> (usize, usize) {
-        validate_inputs(len, deny);
-        let (mut deny_a, mut deny_b) = deny;
-
-        let mut a = self.gen_range(0..len);
-
-        let mut b = if a == deny_a || a == deny_b {
-            // sort ascending: deny_a, deny_b
-            if deny_a > deny_b { mem::swap(&mut deny_a, &mut deny_b); }
-            let ranges = vec![
-                0..deny_a,
-                deny_a + 1..deny_b,
-                deny_b + 1..len,
-            ];
-            let idx = sample_weighted(self, 3, |i| {
-                ranges[i].len() as f64
-            }, 1).unwrap().index(0);
-            self.gen_range(ranges[idx].clone())
-        } else {
-            let b = self.gen_range(0..len - 1);
-            if a == b {
-                len - 1
+        DistinctTupleExcept::new(len, deny).sample(self)
+    }
+
+    /// Select `k` distinct indices from 0..len. Return them sorted ascending.
+    /// Never return the given `deny` combination.
+    /// Every possible candidate combination is selected with uniform probability.
+    ///
+    /// Implemented rejection-free via the combinatorial number system: combinations
+    /// are ranked/unranked in colexicographic order, and the denied rank is skipped
+    /// by drawing uniformly from `0..C(len,k)-1` and shifting ranks at or above it
+    /// up by one.
+    fn random_distinct_index_combination_except(
+        &mut self,
+        len: usize,
+        k: usize,
+        deny: &[usize],
+    ) -> Vec<usize> {
+        validate_combination_inputs(len, k, deny);
+        let mut deny_sorted = deny.to_vec();
+        deny_sorted.sort_unstable();
+        let denied_rank = rank_combination(&deny_sorted);
+        let total = binomial(len, k);
+
+        let mut t = self.gen_range(0..total - 1);
+        if t >= denied_rank {
+            t += 1;
+        }
+        unrank_combination(t, len, k)
+    }
+
+    /// Select two distinct indices from 0..len. Return the indices as an ordered tuple.
+    /// Never return any of the given `deny` tuples.
+    /// Every possible candidate tuple is selected with uniform probability.
+    fn random_distinct_index_tuple_ordered_except_many(
+        &mut self,
+        len: usize,
+        deny: &[(usize, usize)],
+    ) -> (usize, usize) {
+        validate_many_inputs(len, deny);
+        let total = len * (len - 1) / 2;
+
+        let denied_ranks: BTreeSet<usize> = deny
+            .iter()
+            .map(|&(a, b)| {
+                let (i, j) = if a < b { (a, b) } else { (b, a) };
+                pair_rank(len, i, j)
+            })
+            .collect();
+        let denied_ranks: Vec<usize> = denied_ranks.into_iter().collect();
+
+        if denied_ranks.len() >= total {
+            panic!("deny list covers every candidate tuple")
+        }
+
+        let mut t = self.gen_range(0..total - denied_ranks.len());
+        for &r in &denied_ranks {
+            if r <= t {
+                t += 1;
             } else {
-                b
+                break;
             }
-        };
+        }
+        pair_unrank(t, len)
+    }
 
-        if a > b { mem::swap(&mut a, &mut b); }
-        (a, b)
+    /// Select two distinct indices from 0..weights.len(), each index `i` drawn
+    /// with probability proportional to `weights[i]`. Return the indices as an
+    /// ordered tuple. Never return the given `deny` tuple.
+    ///
+    /// The first index is drawn from a `WeightedIndex` over all weights; the
+    /// second is drawn from a `WeightedIndex` over the remaining indices with
+    /// only the first index zeroed out. The ordered pair is rejected and
+    /// redrawn if it equals `deny`.
+    fn random_distinct_index_tuple_weighted_except(
+        &mut self,
+        weights: &[f64],
+        deny: (usize, usize),
+    ) -> (usize, usize) {
+        validate_weighted_inputs(weights, deny);
+        let mut deny_sorted = deny;
+        if deny_sorted.0 > deny_sorted.1 { mem::swap(&mut deny_sorted.0, &mut deny_sorted.1); }
+
+        let first_index = WeightedIndex::new(weights).expect("weights must be valid");
+        loop {
+            let i = first_index.sample(self);
+            let mut remaining = weights.to_vec();
+            remaining[i] = 0.0;
+            let second_index = WeightedIndex::new(&remaining)
+                .expect("at least one index must remain with non-zero weight");
+            let j = second_index.sample(self);
+
+            let (a, b) = if i < j { (i, j) } else { (j, i) };
+            if (a, b) != deny_sorted {
+                return (a, b);
+            }
+        }
+    }
+
+    /// Enumerate every non-denied ordered tuple over `0..len` exactly once, in a
+    /// uniformly random order. Unlike collecting and shuffling a `Vec`, this is
+    /// lazy and uses `O(1)` memory per item yielded rather than `O(C(len, 2))`
+    /// up front.
+    fn shuffled_distinct_tuples_except(
+        &mut self,
+        len: usize,
+        deny: (usize, usize),
+    ) -> ShuffledDistinctTuples<'_, Self>
+    where
+        Self: Sized,
+    {
+        validate_inputs(len, deny);
+        let (a, b) = if deny.0 < deny.1 { deny } else { (deny.1, deny.0) };
+        ShuffledDistinctTuples {
+            rng: self,
+            len,
+            deny_rank: pair_rank(len, a, b),
+            n: len * (len - 1) / 2,
+            remap: HashMap::new(),
+        }
     }
 }
 
@@ -99,8 +426,9 @@ mod test {
     use std::collections::{HashMap, BTreeMap, BTreeSet};
     use rand_pcg::Pcg64;
     use rand::SeedableRng;
+    use rand::distributions::Distribution;
     use maplit::btreemap;
-    use crate::rand_indices::RngExt;
+    use crate::rand_indices::{RngExt, DistinctTupleExcept};
 
     #[test]
     fn random_distinct_index_tuple_ordered_except_good_ok_cases() {
@@ -111,7 +439,7 @@ mod test {
             deny: (usize, usize),
             expected_dist: BTreeMap<(usize, usize), usize>,
         }
-        let tests = vec![
+        let tests = [
             TestCase {
                 name: "three elems, start&end denied",
                 input_len: 3,
@@ -162,8 +490,7 @@ mod test {
                 },
             },
         ];
-        for i in 0..tests.len() {
-            let test = &tests[i];
+        for test in &tests {
             let actual_dist = repeat_and_collect(|| {
                 rng.random_distinct_index_tuple_ordered_except_good(test.input_len, test.deny)
             });
@@ -174,8 +501,7 @@ mod test {
                 test.name,
             );
         }
-        for i in 0..tests.len() {
-            let test = &tests[i];
+        for test in &tests {
             let actual_dist = repeat_and_collect(|| {
                 rng.random_distinct_index_tuple_ordered_except_good(test.input_len, test.deny)
             });
@@ -192,7 +518,7 @@ mod test {
             deny: (usize, usize),
             expected_dist: BTreeMap<(usize, usize), usize>,
         }
-        let tests = vec![
+        let tests = [
             TestCase {
                 name: "three elems, start&end denied",
                 input_len: 3,
@@ -243,8 +569,7 @@ mod test {
                 },
             },
         ];
-        for i in 0..tests.len() {
-            let test = &tests[i];
+        for test in &tests {
             let actual_dist = repeat_and_collect(|| {
                 rng.random_distinct_index_tuple_ordered_except_fast(test.input_len, test.deny)
             });
@@ -255,8 +580,7 @@ mod test {
                 test.name,
             );
         }
-        for i in 0..tests.len() {
-            let test = &tests[i];
+        for test in &tests {
             let actual_dist = repeat_and_collect(|| {
                 rng.random_distinct_index_tuple_ordered_except_fast(test.input_len, test.deny)
             });
@@ -264,6 +588,263 @@ mod test {
         }
     }
 
+    #[test]
+    fn distinct_tuple_except_ok_cases() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        struct TestCase {
+            name: &'static str,
+            input_len: usize,
+            deny: (usize, usize),
+            expected_dist: BTreeMap<(usize, usize), usize>,
+        }
+        let tests = [
+            TestCase {
+                name: "four elems, start&end denied",
+                input_len: 4,
+                deny: (0, 3),
+                expected_dist: btreemap! {
+                    (0,1) => 20,
+                    (0,2) => 20,
+                    (1,2) => 20,
+                    (1,3) => 20,
+                    (2,3) => 20,
+                },
+            },
+            TestCase {
+                name: "five elems, two adjacent denied",
+                input_len: 5,
+                deny: (1, 2),
+                expected_dist: btreemap! {
+                    (0,1) => 11,
+                    (0,2) => 11,
+                    (0,3) => 11,
+                    (0,4) => 11,
+                    (1,3) => 11,
+                    (1,4) => 11,
+                    (2,3) => 11,
+                    (2,4) => 11,
+                    (3,4) => 11,
+                },
+            },
+        ];
+        for test in &tests {
+            let distribution = DistinctTupleExcept::new(test.input_len, test.deny);
+            let actual_dist = repeat_and_collect(|| distribution.sample(&mut rng));
+            assert_eq!(
+                test.expected_dist.keys().collect::<BTreeSet<_>>(),
+                actual_dist.keys().collect::<BTreeSet<_>>(),
+                "test {} failed",
+                test.name,
+            );
+        }
+        for test in &tests {
+            let distribution = DistinctTupleExcept::new(test.input_len, test.deny);
+            let actual_dist = repeat_and_collect(|| distribution.sample(&mut rng));
+            assert_eq!(test.expected_dist, actual_dist, "test {} failed", test.name);
+        }
+    }
+
+    #[test]
+    fn shuffled_distinct_tuples_except_yields_every_non_denied_tuple_exactly_once() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        struct TestCase {
+            name: &'static str,
+            input_len: usize,
+            deny: (usize, usize),
+            expected: BTreeSet<(usize, usize)>,
+        }
+        let tests = [
+            TestCase {
+                name: "four elems, start&end denied",
+                input_len: 4,
+                deny: (0, 3),
+                expected: btreeset_of(&[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]),
+            },
+            TestCase {
+                name: "five elems, two adjacent denied",
+                input_len: 5,
+                deny: (1, 2),
+                expected: btreeset_of(&[
+                    (0, 1), (0, 2), (0, 3), (0, 4), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4),
+                ]),
+            },
+        ];
+        for test in &tests {
+            let yielded: Vec<(usize, usize)> =
+                rng.shuffled_distinct_tuples_except(test.input_len, test.deny).collect();
+            let unique: BTreeSet<(usize, usize)> = yielded.iter().copied().collect();
+            assert_eq!(unique.len(), yielded.len(), "test {} yielded a duplicate", test.name);
+            assert_eq!(unique, test.expected, "test {} failed", test.name);
+        }
+    }
+
+    fn btreeset_of(pairs: &[(usize, usize)]) -> BTreeSet<(usize, usize)> {
+        pairs.iter().copied().collect()
+    }
+
+    #[test]
+    fn random_distinct_index_tuple_weighted_except_never_returns_zero_weight_or_deny() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        // index 0 is heavily favored, index 4 has no weight at all, (1, 2) is denied.
+        let weights = vec![5.0, 1.0, 1.0, 1.0, 0.0];
+        let deny = (1, 2);
+        let mut appearances = 0;
+        for _ in 0..10_000 {
+            let (a, b) = rng.random_distinct_index_tuple_weighted_except(&weights, deny);
+            assert_ne!((a, b), deny);
+            assert_ne!(a, 4, "index with zero weight must never be selected");
+            assert_ne!(b, 4, "index with zero weight must never be selected");
+            assert!(a < b);
+            if a == 0 || b == 0 {
+                appearances += 1;
+            }
+        }
+        // index 0's weight dominates, it should show up in most draws
+        assert!(appearances > 8_000, "heavily weighted index appeared {appearances}/10000 times");
+    }
+
+    #[test]
+    fn random_distinct_index_tuple_weighted_except_only_suppresses_the_denied_pair() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        // deny only forbids (1, 2); (0, 1) and (0, 2) share the same weight product
+        // as (0, 3) and must appear about as often, since denying a pair must not
+        // also suppress its indices from pairing with anything else.
+        let weights = vec![5.0, 1.0, 1.0, 1.0, 0.0];
+        let deny = (1, 2);
+        let mut counts: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for _ in 0..300_000 {
+            let pair = rng.random_distinct_index_tuple_weighted_except(&weights, deny);
+            *counts.entry(pair).or_insert(0) += 1;
+        }
+        let count = |pair: (usize, usize)| *counts.get(&pair).unwrap_or(&0) as f64;
+        let base = count((0, 3));
+        for pair in [(0, 1), (0, 2)] {
+            let ratio = count(pair) / base;
+            assert!(
+                (0.85..1.15).contains(&ratio),
+                "{:?} appeared {} times vs {} for (0,3), ratio {:.2}",
+                pair, count(pair), base, ratio,
+            );
+        }
+    }
+
+    #[test]
+    fn random_distinct_index_tuple_ordered_except_many_ok_cases() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        struct TestCase {
+            name: &'static str,
+            input_len: usize,
+            deny: Vec<(usize, usize)>,
+            expected_dist: BTreeMap<(usize, usize), usize>,
+        }
+        let tests = [
+            TestCase {
+                name: "four elems, two denied (one given out of order)",
+                input_len: 4,
+                deny: vec![(0, 3), (2, 1)],
+                expected_dist: btreemap! {
+                    (0,1) => 25,
+                    (0,2) => 25,
+                    (1,3) => 25,
+                    (2,3) => 25,
+                },
+            },
+            TestCase {
+                name: "five elems, duplicate denied entries",
+                input_len: 5,
+                deny: vec![(1, 2), (1, 2), (0, 4)],
+                expected_dist: btreemap! {
+                    (0,1) => 12,
+                    (0,2) => 13,
+                    (0,3) => 12,
+                    (1,3) => 13,
+                    (1,4) => 13,
+                    (2,3) => 13,
+                    (2,4) => 12,
+                    (3,4) => 13,
+                },
+            },
+        ];
+        for test in &tests {
+            let actual_dist = repeat_and_collect(|| {
+                rng.random_distinct_index_tuple_ordered_except_many(test.input_len, &test.deny)
+            });
+            assert_eq!(
+                test.expected_dist.keys().collect::<BTreeSet<_>>(),
+                actual_dist.keys().collect::<BTreeSet<_>>(),
+                "test {} failed",
+                test.name,
+            );
+        }
+        for test in &tests {
+            let actual_dist = repeat_and_collect(|| {
+                rng.random_distinct_index_tuple_ordered_except_many(test.input_len, &test.deny)
+            });
+            assert_eq!(test.expected_dist, actual_dist, "test {} failed", test.name);
+        }
+    }
+
+    #[test]
+    fn random_distinct_index_combination_except_ok_cases() {
+        let mut rng = Pcg64::seed_from_u64(1);
+        struct TestCase {
+            name: &'static str,
+            input_len: usize,
+            k: usize,
+            deny: Vec<usize>,
+            expected_dist: BTreeMap<Vec<usize>, usize>,
+        }
+        let tests = [
+            TestCase {
+                name: "four elems, pairs, one denied",
+                input_len: 4,
+                k: 2,
+                deny: vec![0, 2],
+                expected_dist: btreemap! {
+                    vec![0,1] => 20,
+                    vec![0,3] => 20,
+                    vec![1,2] => 20,
+                    vec![1,3] => 20,
+                    vec![2,3] => 20,
+                },
+            },
+            TestCase {
+                name: "five elems, triples, one denied",
+                input_len: 5,
+                k: 3,
+                deny: vec![0, 1, 2],
+                expected_dist: btreemap! {
+                    vec![0,1,3] => 11,
+                    vec![0,1,4] => 11,
+                    vec![0,2,3] => 11,
+                    vec![0,2,4] => 11,
+                    vec![0,3,4] => 11,
+                    vec![1,2,3] => 11,
+                    vec![1,2,4] => 11,
+                    vec![1,3,4] => 11,
+                    vec![2,3,4] => 11,
+                },
+            },
+        ];
+        for test in &tests {
+            let actual_dist = repeat_and_collect(|| {
+                rng.random_distinct_index_combination_except(test.input_len, test.k, &test.deny)
+            });
+            assert_eq!(
+                test.expected_dist.keys().collect::<BTreeSet<_>>(),
+                actual_dist.keys().collect::<BTreeSet<_>>(),
+                "test {} failed",
+                test.name,
+            );
+        }
+        for test in &tests {
+            let actual_dist = repeat_and_collect(|| {
+                rng.random_distinct_index_combination_except(test.input_len, test.k, &test.deny)
+            });
+            assert_eq!(test.expected_dist, actual_dist, "test {} failed", test.name);
+        }
+    }
+
     pub fn repeat_and_collect<T, F>(mut function: F) -> BTreeMap<T, usize>
         where
             T: Eq + Hash + Ord,